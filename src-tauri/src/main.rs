@@ -4,13 +4,20 @@ mod secure_storage;
 
 fn main() {
     tauri::Builder::default()
+        .manage(secure_storage::VaultState::default())
         .invoke_handler(tauri::generate_handler![
+            secure_storage::secure_unlock,
+            secure_storage::secure_lock,
+            secure_storage::secure_session_status,
             secure_storage::secure_save_api_config,
             secure_storage::secure_load_api_config,
             secure_storage::secure_clear_api_config,
+            secure_storage::secure_list_profiles,
             secure_storage::secure_save_stt_config,
             secure_storage::secure_load_stt_config,
-            secure_storage::secure_clear_stt_config
+            secure_storage::secure_clear_stt_config,
+            secure_storage::secure_export_bundle,
+            secure_storage::secure_import_bundle
         ])
         .run(tauri::generate_context!())
         .expect("error while running IntelliNote desktop application");
@@ -1,10 +1,84 @@
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
 use keyring::Entry;
-use serde::{Deserialize, Serialize};
+use keyring_search::{List, Limit, Search};
+use rand_core::RngCore;
+use serde::{de::DeserializeOwned, ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use thiserror::Error;
 
 const SERVICE_NAME: &str = "IntelliNoteSecureConfig";
-const ACCOUNT_NAME: &str = "api_config";
+const STT_SERVICE_NAME: &str = "IntelliNoteSecureSttConfig";
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Account name used before profile support existed; kept so installs with a single
+/// pre-existing keyring entry keep working without a migration step.
+pub const DEFAULT_PROFILE: &str = "api_config";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Command error, serialized as `{ kind, message }` so the frontend can switch on `kind`
+/// instead of string-matching a flattened message.
+#[derive(Debug, Error)]
+pub enum SecureStorageError {
+    #[error("keyring backend unavailable: {0}")]
+    Backend(String),
+    #[error("no entry found for this profile")]
+    NotFound,
+    #[error("failed to serialize or deserialize config: {0}")]
+    Serialization(String),
+    #[error("failed to decrypt vault entry: {0}")]
+    Decrypt(String),
+    #[error("vault is locked")]
+    Locked,
+}
+
+impl SecureStorageError {
+    fn kind(&self) -> &'static str {
+        match self {
+            SecureStorageError::Backend(_) => "Backend",
+            SecureStorageError::NotFound => "NotFound",
+            SecureStorageError::Serialization(_) => "Serialization",
+            SecureStorageError::Decrypt(_) => "Decrypt",
+            SecureStorageError::Locked => "Locked",
+        }
+    }
+}
+
+impl Serialize for SecureStorageError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("SecureStorageError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<keyring::Error> for SecureStorageError {
+    fn from(err: keyring::Error) -> Self {
+        match err {
+            keyring::Error::NoEntry => SecureStorageError::NotFound,
+            other => SecureStorageError::Backend(other.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for SecureStorageError {
+    fn from(err: serde_json::Error) -> Self {
+        SecureStorageError::Serialization(err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecureApiConfig {
     pub provider: String,
     pub api_key: String,
@@ -12,38 +86,515 @@ pub struct SecureApiConfig {
     pub base_url: Option<String>,
 }
 
-fn keyring_entry() -> Result<Entry, keyring::Error> {
-    Entry::new(SERVICE_NAME, ACCOUNT_NAME)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecureSttConfig {
+    pub provider: String,
+    pub api_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+/// Passphrase-derived key plus the salt it was derived from, held in memory
+/// only while the profile's vault is unlocked.
+struct VaultKey {
+    salt: [u8; SALT_LEN],
+    key: [u8; KEY_LEN],
+}
+
+#[derive(Default)]
+pub struct VaultState(RwLock<HashMap<String, VaultKey>>);
+
+fn keyring_entry(service: &str, profile: &str) -> Result<Entry, SecureStorageError> {
+    Ok(Entry::new(service, profile)?)
 }
 
+/// Falls back to `DEFAULT_PROFILE` when the caller doesn't specify one, so installs that
+/// predate profile support keep reading/writing the single pre-existing keyring entry.
+fn resolve_profile(profile: Option<String>) -> String {
+    profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8; SALT_LEN]) -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("argon2id key derivation should not fail for a fixed-size output");
+    key
+}
+
+/// Encrypts `value` under `key`/`salt`, returning the base64 `salt || nonce || ciphertext` blob.
+fn encrypt_blob<T: Serialize>(
+    value: &T,
+    salt: &[u8; SALT_LEN],
+    key: &[u8; KEY_LEN],
+) -> Result<String, SecureStorageError> {
+    let plaintext = serde_json::to_vec(value)?;
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), plaintext.as_ref())
+        .map_err(|e| SecureStorageError::Decrypt(e.to_string()))?;
+
+    let mut blob = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(salt);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(blob))
+}
+
+/// Decodes a base64 `salt || nonce || ciphertext` blob into its parts.
+fn split_blob(raw: &str) -> Result<([u8; SALT_LEN], Vec<u8>, Vec<u8>), SecureStorageError> {
+    let blob = STANDARD
+        .decode(raw)
+        .map_err(|e| SecureStorageError::Decrypt(e.to_string()))?;
+    if blob.len() < SALT_LEN + NONCE_LEN {
+        return Err(SecureStorageError::Decrypt("corrupt vault entry".to_string()));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    salt.copy_from_slice(&blob[..SALT_LEN]);
+    let nonce = blob[SALT_LEN..SALT_LEN + NONCE_LEN].to_vec();
+    let ciphertext = blob[SALT_LEN + NONCE_LEN..].to_vec();
+    Ok((salt, nonce, ciphertext))
+}
+
+fn decrypt_with_key<T: DeserializeOwned>(
+    nonce: &[u8],
+    ciphertext: &[u8],
+    key: &[u8; KEY_LEN],
+) -> Result<T, SecureStorageError> {
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|e| SecureStorageError::Decrypt(e.to_string()))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Encrypts `value` under `vault`, returning the base64 `salt || nonce || ciphertext` blob.
+fn encrypt_config<T: Serialize>(value: &T, vault: &VaultKey) -> Result<String, SecureStorageError> {
+    encrypt_blob(value, &vault.salt, &vault.key)
+}
+
+/// Decrypts a base64 `salt || nonce || ciphertext` blob. Returns `Locked` when `vault`'s
+/// salt doesn't match the stored entry's, since the key in memory was then derived under a
+/// different salt than the one the entry was saved with and can't decrypt it, whatever the
+/// passphrase.
+fn decrypt_config<T: DeserializeOwned>(raw: &str, vault: &VaultKey) -> Result<T, SecureStorageError> {
+    let (salt, nonce, ciphertext) = split_blob(raw)?;
+    if salt != vault.salt {
+        return Err(SecureStorageError::Locked);
+    }
+    decrypt_with_key(&nonce, &ciphertext, &vault.key)
+}
+
+/// Unlocks `profile` for the process lifetime. If an encrypted entry already exists, the
+/// passphrase is verified by attempting to decrypt it. A legacy plaintext entry is migrated
+/// in place: it's re-saved encrypted under a freshly generated salt and the new passphrase.
+/// If no entry exists yet, a fresh salt is generated and the derived key is cached, ready
+/// for the first `secure_save_api_config`.
+#[tauri::command]
+pub fn secure_unlock(
+    profile: Option<String>,
+    passphrase: String,
+    vault: tauri::State<VaultState>,
+) -> Result<(), SecureStorageError> {
+    let profile = resolve_profile(profile);
+    let entry = keyring_entry(SERVICE_NAME, &profile)?;
+    match entry.get_password() {
+        Ok(raw) => {
+            if let Ok(legacy) = serde_json::from_str::<SecureApiConfig>(&raw) {
+                let mut salt = [0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                let candidate = VaultKey {
+                    salt,
+                    key: derive_key(&passphrase, &salt),
+                };
+                entry.set_password(&encrypt_config(&legacy, &candidate)?)?;
+                vault.0.write().unwrap().insert(profile, candidate);
+                return Ok(());
+            }
+            let (salt, nonce, ciphertext) = split_blob(&raw)?;
+            let candidate = VaultKey {
+                salt,
+                key: derive_key(&passphrase, &salt),
+            };
+            decrypt_with_key::<SecureApiConfig>(&nonce, &ciphertext, &candidate.key)
+                .map_err(|_| SecureStorageError::Decrypt("incorrect passphrase".to_string()))?;
+            vault.0.write().unwrap().insert(profile, candidate);
+            Ok(())
+        }
+        Err(keyring::Error::NoEntry) => {
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive_key(&passphrase, &salt);
+            vault.0.write().unwrap().insert(profile, VaultKey { salt, key });
+            Ok(())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Drops `profile`'s in-memory key, requiring `secure_unlock` again before its next load.
 #[tauri::command]
-pub fn secure_save_api_config(config: SecureApiConfig) -> Result<(), String> {
-    let entry = keyring_entry().map_err(|e| e.to_string())?;
-    let serialized = serde_json::to_string(&config).map_err(|e| e.to_string())?;
-    entry
-        .set_password(&serialized)
-        .map_err(|e| e.to_string())
+pub fn secure_lock(profile: Option<String>, vault: tauri::State<VaultState>) {
+    let profile = resolve_profile(profile);
+    vault.0.write().unwrap().remove(&profile);
 }
 
 #[tauri::command]
-pub fn secure_load_api_config() -> Result<Option<SecureApiConfig>, String> {
-    let entry = keyring_entry().map_err(|e| e.to_string())?;
+pub fn secure_session_status(
+    profile: Option<String>,
+    vault: tauri::State<VaultState>,
+) -> Result<String, SecureStorageError> {
+    let profile = resolve_profile(profile);
+    if vault.0.read().unwrap().contains_key(&profile) {
+        return Ok("unlocked".to_string());
+    }
+    match keyring_entry(SERVICE_NAME, &profile)?.get_password() {
+        Ok(raw) if serde_json::from_str::<SecureApiConfig>(&raw).is_ok() => {
+            Ok("unlocked".to_string())
+        }
+        Ok(_) => Ok("locked".to_string()),
+        Err(keyring::Error::NoEntry) => Ok("empty".to_string()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[tauri::command]
+pub fn secure_save_api_config(
+    profile: Option<String>,
+    config: SecureApiConfig,
+    vault: tauri::State<VaultState>,
+) -> Result<(), SecureStorageError> {
+    let profile = resolve_profile(profile);
+    let entry = keyring_entry(SERVICE_NAME, &profile)?;
+    match vault.0.read().unwrap().get(&profile) {
+        Some(vk) => Ok(entry.set_password(&encrypt_config(&config, vk)?)?),
+        None => {
+            // An existing entry that isn't legacy plaintext is vault-encrypted; refuse to
+            // stomp it with a plaintext write just because this profile isn't unlocked.
+            if let Ok(raw) = entry.get_password() {
+                if serde_json::from_str::<SecureApiConfig>(&raw).is_err() {
+                    return Err(SecureStorageError::Locked);
+                }
+            }
+            let serialized = serde_json::to_string(&config)?;
+            Ok(entry.set_password(&serialized)?)
+        }
+    }
+}
+
+fn load_api_config(
+    profile: &str,
+    unlocked: &HashMap<String, VaultKey>,
+) -> Result<Option<SecureApiConfig>, SecureStorageError> {
+    let entry = keyring_entry(SERVICE_NAME, profile)?;
     match entry.get_password() {
         Ok(raw) => {
-            let parsed: SecureApiConfig =
-                serde_json::from_str(&raw).map_err(|e| e.to_string())?;
-            Ok(Some(parsed))
+            if let Ok(parsed) = serde_json::from_str::<SecureApiConfig>(&raw) {
+                return Ok(Some(parsed));
+            }
+            match unlocked.get(profile) {
+                Some(vk) => decrypt_config(&raw, vk).map(Some),
+                None => Err(SecureStorageError::Locked),
+            }
         }
         Err(keyring::Error::NoEntry) => Ok(None),
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[tauri::command]
+pub fn secure_load_api_config(
+    profile: Option<String>,
+    vault: tauri::State<VaultState>,
+) -> Result<Option<SecureApiConfig>, SecureStorageError> {
+    load_api_config(&resolve_profile(profile), &vault.0.read().unwrap())
+}
+
+#[tauri::command]
+pub fn secure_clear_api_config(profile: Option<String>) -> Result<(), SecureStorageError> {
+    let entry = keyring_entry(SERVICE_NAME, &resolve_profile(profile))?;
+    match entry.delete_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[tauri::command]
+pub fn secure_save_stt_config(
+    profile: Option<String>,
+    config: SecureSttConfig,
+) -> Result<(), SecureStorageError> {
+    let entry = keyring_entry(STT_SERVICE_NAME, &resolve_profile(profile))?;
+    let serialized = serde_json::to_string(&config)?;
+    Ok(entry.set_password(&serialized)?)
+}
+
+fn load_stt_config(profile: &str) -> Result<Option<SecureSttConfig>, SecureStorageError> {
+    let entry = keyring_entry(STT_SERVICE_NAME, profile)?;
+    match entry.get_password() {
+        Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(err.into()),
     }
 }
 
 #[tauri::command]
-pub fn secure_clear_api_config() -> Result<(), String> {
-    let entry = keyring_entry().map_err(|e| e.to_string())?;
+pub fn secure_load_stt_config(profile: Option<String>) -> Result<Option<SecureSttConfig>, SecureStorageError> {
+    load_stt_config(&resolve_profile(profile))
+}
+
+#[tauri::command]
+pub fn secure_clear_stt_config(profile: Option<String>) -> Result<(), SecureStorageError> {
+    let entry = keyring_entry(STT_SERVICE_NAME, &resolve_profile(profile))?;
     match entry.delete_password() {
         Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn list_profiles_for_service(service: &str) -> Result<Vec<String>, SecureStorageError> {
+    let search = Search::new().map_err(|e| SecureStorageError::Backend(e.to_string()))?;
+    let results = search.by_service(service);
+    let credentials = List::list_credentials(&results, Limit::All);
+    Ok(credentials
+        .lines()
+        .filter_map(|line| line.split_once(" account: "))
+        .map(|(_, account)| account.trim().to_string())
+        .collect())
+}
+
+/// Enumerates profile names with a stored entry under `SERVICE_NAME`, for an account
+/// switcher UI. Relies on the platform keyring search backend, so results may be empty
+/// on platforms `keyring-search` doesn't support rather than erroring.
+#[tauri::command]
+pub fn secure_list_profiles() -> Result<Vec<String>, SecureStorageError> {
+    list_profiles_for_service(SERVICE_NAME)
+}
+
+/// Everything that can live in an export bundle, keyed by profile name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ConfigBundle {
+    api_configs: HashMap<String, SecureApiConfig>,
+    stt_configs: HashMap<String, SecureSttConfig>,
+}
+
+/// Report of what an import did, so the frontend can show the user a summary.
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+}
+
+/// Result of an export: the encrypted bundle plus any profiles that couldn't be read
+/// because their vault is locked, so the caller knows the bundle may be incomplete.
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    pub bundle: String,
+    pub skipped: Vec<String>,
+}
+
+/// Serializes every api/stt profile this process can currently read into a single bundle,
+/// encrypted with `passphrase` using the same Argon2id + XChaCha20-Poly1305 scheme as the
+/// vault, and returns it as a base64 string the caller can save to a file. Profiles whose
+/// vault is locked are excluded from the bundle, since there's no key available to read
+/// them, and are listed in `ExportResult::skipped` so the caller knows the backup is
+/// incomplete.
+#[tauri::command]
+pub fn secure_export_bundle(
+    passphrase: String,
+    vault: tauri::State<VaultState>,
+) -> Result<ExportResult, SecureStorageError> {
+    let mut bundle = ConfigBundle::default();
+    let mut skipped = Vec::new();
+    let unlocked = vault.0.read().unwrap();
+
+    for profile in list_profiles_for_service(SERVICE_NAME)? {
+        match load_api_config(&profile, &unlocked) {
+            Ok(Some(config)) => {
+                bundle.api_configs.insert(profile, config);
+            }
+            Ok(None) => {}
+            Err(SecureStorageError::Locked) => skipped.push(profile),
+            Err(err) => return Err(err),
+        }
+    }
+    for profile in list_profiles_for_service(STT_SERVICE_NAME)? {
+        match load_stt_config(&profile) {
+            Ok(Some(config)) => {
+                bundle.stt_configs.insert(profile, config);
+            }
+            Ok(None) => {}
+            Err(SecureStorageError::Locked) => skipped.push(profile),
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(&passphrase, &salt);
+    let bundle = encrypt_blob(&bundle, &salt, &key)?;
+    Ok(ExportResult { bundle, skipped })
+}
+
+/// Decrypts a bundle produced by `secure_export_bundle` and writes its profiles back into
+/// the keyring, reporting which profile names were newly added versus overwritten.
+#[tauri::command]
+pub fn secure_import_bundle(
+    bundle: String,
+    passphrase: String,
+    vault: tauri::State<VaultState>,
+) -> Result<ImportReport, SecureStorageError> {
+    let (salt, nonce, ciphertext) = split_blob(&bundle)?;
+    let key = derive_key(&passphrase, &salt);
+    let parsed: ConfigBundle = decrypt_with_key(&nonce, &ciphertext, &key)
+        .map_err(|_| SecureStorageError::Decrypt("incorrect passphrase".to_string()))?;
+
+    let mut report = ImportReport {
+        added: Vec::new(),
+        overwritten: Vec::new(),
+    };
+
+    let unlocked = vault.0.read().unwrap();
+    for (profile, config) in parsed.api_configs {
+        let entry = keyring_entry(SERVICE_NAME, &profile)?;
+        let existing = entry.get_password();
+        let existed = existing.is_ok();
+        // Mirror secure_save_api_config: re-encrypt into profiles whose vault is already
+        // unlocked. If the profile isn't unlocked but already has an encrypted entry, refuse
+        // to stomp it with a plaintext write instead of silently downgrading it.
+        match unlocked.get(&profile) {
+            Some(vk) => entry.set_password(&encrypt_config(&config, vk)?)?,
+            None => {
+                if let Ok(raw) = &existing {
+                    if serde_json::from_str::<SecureApiConfig>(raw).is_err() {
+                        return Err(SecureStorageError::Locked);
+                    }
+                }
+                entry.set_password(&serde_json::to_string(&config)?)?;
+            }
+        }
+        if existed {
+            report.overwritten.push(profile);
+        } else {
+            report.added.push(profile);
+        }
+    }
+    for (profile, config) in parsed.stt_configs {
+        let entry = keyring_entry(STT_SERVICE_NAME, &profile)?;
+        let existed = entry.get_password().is_ok();
+        entry.set_password(&serde_json::to_string(&config)?)?;
+        if existed {
+            report.overwritten.push(profile);
+        } else {
+            report.added.push(profile);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_config() -> SecureApiConfig {
+        SecureApiConfig {
+            provider: "openai".to_string(),
+            api_key: "sk-test-123".to_string(),
+            base_url: None,
+        }
+    }
+
+    fn vault_key_for(passphrase: &str, salt: [u8; SALT_LEN]) -> VaultKey {
+        VaultKey {
+            salt,
+            key: derive_key(passphrase, &salt),
+        }
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let vault = vault_key_for("correct horse battery staple", [7u8; SALT_LEN]);
+        let config = sample_config();
+
+        let blob = encrypt_config(&config, &vault).expect("encrypt should succeed");
+        let decrypted: SecureApiConfig =
+            decrypt_config(&blob, &vault).expect("decrypt should succeed with the same vault key");
+
+        assert_eq!(decrypted.provider, config.provider);
+        assert_eq!(decrypted.api_key, config.api_key);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_to_decrypt() {
+        let salt = [3u8; SALT_LEN];
+        let vault = vault_key_for("right passphrase", salt);
+        let blob = encrypt_config(&sample_config(), &vault).unwrap();
+
+        let wrong_vault = vault_key_for("wrong passphrase", salt);
+        let result: Result<SecureApiConfig, _> = decrypt_config(&blob, &wrong_vault);
+
+        assert!(matches!(result, Err(SecureStorageError::Decrypt(_))));
+    }
+
+    #[test]
+    fn mismatched_salt_reports_locked() {
+        let vault = vault_key_for("passphrase-a", [1u8; SALT_LEN]);
+        let blob = encrypt_config(&sample_config(), &vault).unwrap();
+
+        let other_profile_vault = vault_key_for("passphrase-b", [2u8; SALT_LEN]);
+        let result: Result<SecureApiConfig, _> = decrypt_config(&blob, &other_profile_vault);
+
+        assert!(matches!(result, Err(SecureStorageError::Locked)));
+    }
+
+    #[test]
+    fn distinct_profiles_derive_distinct_keys() {
+        let profile_a = vault_key_for("shared passphrase", [9u8; SALT_LEN]);
+        let profile_b = vault_key_for("shared passphrase", [10u8; SALT_LEN]);
+
+        assert_ne!(profile_a.key, profile_b.key);
+
+        let blob = encrypt_config(&sample_config(), &profile_a).unwrap();
+        let result: Result<SecureApiConfig, _> = decrypt_config(&blob, &profile_b);
+        assert!(matches!(result, Err(SecureStorageError::Locked)));
+    }
+
+    #[test]
+    fn export_bundle_scheme_round_trips_through_import() {
+        let mut bundle = ConfigBundle::default();
+        bundle
+            .api_configs
+            .insert("openai-personal".to_string(), sample_config());
+        bundle.stt_configs.insert(
+            "whisper-work".to_string(),
+            SecureSttConfig {
+                provider: "whisper".to_string(),
+                api_key: "stt-key".to_string(),
+                base_url: Some("https://stt.example.com".to_string()),
+            },
+        );
+
+        let mut salt = [0u8; SALT_LEN];
+        salt[0] = 42;
+        let key = derive_key("bundle passphrase", &salt);
+        let encoded = encrypt_blob(&bundle, &salt, &key).expect("bundle should encrypt");
+
+        let (decoded_salt, nonce, ciphertext) = split_blob(&encoded).unwrap();
+        assert_eq!(decoded_salt, salt);
+        let decoded_key = derive_key("bundle passphrase", &decoded_salt);
+        let round_tripped: ConfigBundle =
+            decrypt_with_key(&nonce, &ciphertext, &decoded_key).expect("bundle should decrypt");
+
+        assert_eq!(
+            round_tripped.api_configs["openai-personal"].api_key,
+            "sk-test-123"
+        );
+        assert_eq!(
+            round_tripped.stt_configs["whisper-work"].base_url,
+            Some("https://stt.example.com".to_string())
+        );
     }
 }